@@ -1,5 +1,9 @@
 use wavers::Samples;
 
+pub mod features;
+pub mod integrity;
+pub mod loudness;
+pub mod peak;
 pub mod silence;
 pub mod underruns;
 
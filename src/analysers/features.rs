@@ -0,0 +1,226 @@
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use serde::Serialize;
+use std::sync::Arc;
+use wavers::Samples;
+
+use super::Analyser;
+use crate::output;
+use crate::source::FrameSource;
+
+const WINDOW_SIZE: usize = 2048;
+const HOP_SIZE: usize = WINDOW_SIZE / 2;
+const MIN_BPM: f64 = 60.0;
+const MAX_BPM: f64 = 200.0;
+
+#[derive(Serialize)]
+pub struct FeatureDescriptor {
+    #[serde(rename = "spectralCentroidMean")]
+    pub spectral_centroid_mean: f64,
+    #[serde(rename = "spectralCentroidVariance")]
+    pub spectral_centroid_variance: f64,
+    #[serde(rename = "spectralRolloffMean")]
+    pub spectral_rolloff_mean: f64,
+    #[serde(rename = "spectralRolloffVariance")]
+    pub spectral_rolloff_variance: f64,
+    #[serde(rename = "zeroCrossingRateMean")]
+    pub zero_crossing_rate_mean: f64,
+    #[serde(rename = "zeroCrossingRateVariance")]
+    pub zero_crossing_rate_variance: f64,
+    #[serde(rename = "estimatedBpm")]
+    pub estimated_bpm: Option<f64>,
+}
+
+pub struct FeatureAnalyser {
+    fft: Arc<dyn Fft<f32>>,
+    hann_window: Vec<f32>,
+    mono_buffer: Vec<f32>,
+    n_channels: usize,
+    sample_rate: i32,
+    prev_magnitudes: Option<Vec<f32>>,
+    centroids: Vec<f64>,
+    rolloffs: Vec<f64>,
+    zero_crossing_rates: Vec<f64>,
+    flux_envelope: Vec<f64>,
+}
+
+impl FeatureAnalyser {
+    pub fn new(_args: &crate::cli::Cli, source: &dyn FrameSource) -> Self {
+        let mut planner = FftPlanner::new();
+        let fft = planner.plan_fft_forward(WINDOW_SIZE);
+
+        let hann_window = (0..WINDOW_SIZE)
+            .map(|n| {
+                0.5 * (1.0
+                    - (2.0 * std::f32::consts::PI * n as f32 / (WINDOW_SIZE as f32 - 1.0)).cos())
+            })
+            .collect();
+
+        Self {
+            fft,
+            hann_window,
+            mono_buffer: Vec::new(),
+            n_channels: source.n_channels() as usize,
+            sample_rate: source.sample_rate(),
+            prev_magnitudes: None,
+            centroids: Vec::new(),
+            rolloffs: Vec::new(),
+            zero_crossing_rates: Vec::new(),
+            flux_envelope: Vec::new(),
+        }
+    }
+
+    fn analyse_window(&mut self) {
+        let window: Vec<Complex<f32>> = self.mono_buffer[..WINDOW_SIZE]
+            .iter()
+            .zip(&self.hann_window)
+            .map(|(sample, coefficient)| Complex::new(sample * coefficient, 0.0))
+            .collect();
+
+        let mut spectrum = window;
+        self.fft.process(&mut spectrum);
+
+        let bins = WINDOW_SIZE / 2;
+        let magnitudes: Vec<f32> = spectrum[..bins].iter().map(|bin| bin.norm()).collect();
+        let bin_hz = self.sample_rate as f64 / WINDOW_SIZE as f64;
+
+        let total_energy: f64 = magnitudes.iter().map(|&m| m as f64).sum();
+        let centroid = if total_energy > 0.0 {
+            magnitudes
+                .iter()
+                .enumerate()
+                .map(|(bin, &m)| bin as f64 * bin_hz * m as f64)
+                .sum::<f64>()
+                / total_energy
+        } else {
+            0.0
+        };
+
+        let mut cumulative_energy = 0.0;
+        let mut rolloff_bin = bins.saturating_sub(1);
+        for (bin, &m) in magnitudes.iter().enumerate() {
+            cumulative_energy += m as f64;
+            if total_energy > 0.0 && cumulative_energy / total_energy >= 0.85 {
+                rolloff_bin = bin;
+                break;
+            }
+        }
+        let rolloff = rolloff_bin as f64 * bin_hz;
+
+        let zero_crossings = self.mono_buffer[..WINDOW_SIZE]
+            .windows(2)
+            .filter(|pair| pair[0].signum() != pair[1].signum())
+            .count();
+        let zero_crossing_rate = zero_crossings as f64 / WINDOW_SIZE as f64;
+
+        let flux = match &self.prev_magnitudes {
+            Some(prev) => magnitudes
+                .iter()
+                .zip(prev)
+                .map(|(&current, &previous)| (current - previous).max(0.0) as f64)
+                .sum(),
+            None => 0.0,
+        };
+
+        self.centroids.push(centroid);
+        self.rolloffs.push(rolloff);
+        self.zero_crossing_rates.push(zero_crossing_rate);
+        self.flux_envelope.push(flux);
+        self.prev_magnitudes = Some(magnitudes);
+
+        self.mono_buffer.drain(..HOP_SIZE);
+    }
+
+    fn estimate_bpm(&self) -> Option<f64> {
+        let hop_duration = HOP_SIZE as f64 / self.sample_rate as f64;
+        let min_lag = (60.0 / MAX_BPM / hop_duration).round() as usize;
+        let max_lag = (60.0 / MIN_BPM / hop_duration).round() as usize;
+
+        if self.flux_envelope.len() <= max_lag.max(1) {
+            return None;
+        }
+
+        let mean = self.flux_envelope.iter().sum::<f64>() / self.flux_envelope.len() as f64;
+        let centered: Vec<f64> = self.flux_envelope.iter().map(|&v| v - mean).collect();
+
+        (min_lag.max(1)..=max_lag)
+            .map(|lag| {
+                let correlation: f64 = centered[..centered.len() - lag]
+                    .iter()
+                    .zip(&centered[lag..])
+                    .map(|(a, b)| a * b)
+                    .sum();
+                (lag, correlation)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(lag, _)| 60.0 / (lag as f64 * hop_duration))
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn variance(values: &[f64], mean: f64) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    }
+}
+
+impl Analyser for FeatureAnalyser {
+    fn analyse(&mut self, _label: &str, _frame_counter: usize, frame: &Samples<i32>) {
+        let downmixed = frame.iter().map(|sample| *sample as f64).sum::<f64>()
+            / self.n_channels as f64
+            / i32::MAX as f64;
+        self.mono_buffer.push(downmixed as f32);
+
+        while self.mono_buffer.len() >= WINDOW_SIZE {
+            self.analyse_window();
+        }
+    }
+
+    fn finish(&mut self, label: &str) -> u8 {
+        let centroid_mean = mean(&self.centroids);
+        let rolloff_mean = mean(&self.rolloffs);
+        let zcr_mean = mean(&self.zero_crossing_rates);
+        let bpm = self.estimate_bpm();
+
+        output!(
+            "[{}] FEATURES     : centroid: {:.1} Hz; rolloff: {:.1} Hz; ZCR: {:.4}; BPM: {}",
+            label,
+            centroid_mean,
+            rolloff_mean,
+            zcr_mean,
+            bpm.map(|b| format!("{b:.1}")).unwrap_or_else(|| "n/a".to_string())
+        );
+
+        0
+    }
+
+    fn json(&self) -> Option<(String, serde_json::Value)> {
+        let centroid_mean = mean(&self.centroids);
+        let rolloff_mean = mean(&self.rolloffs);
+        let zcr_mean = mean(&self.zero_crossing_rates);
+
+        let descriptor = FeatureDescriptor {
+            spectral_centroid_mean: centroid_mean,
+            spectral_centroid_variance: variance(&self.centroids, centroid_mean),
+            spectral_rolloff_mean: rolloff_mean,
+            spectral_rolloff_variance: variance(&self.rolloffs, rolloff_mean),
+            zero_crossing_rate_mean: zcr_mean,
+            zero_crossing_rate_variance: variance(&self.zero_crossing_rates, zcr_mean),
+            estimated_bpm: self.estimate_bpm(),
+        };
+
+        Some((
+            "features".to_string(),
+            serde_json::to_value(descriptor).unwrap(),
+        ))
+    }
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+use wavers::Samples;
+
+use super::Analyser;
+use crate::source::FrameSource;
+use crate::{output, output::frame_to_time};
+
+const DC_OFFSET_SMOOTHING: f64 = 0.001;
+
+#[derive(Debug, Clone)]
+pub struct IntegrityState {
+    pub running_mean: f64,
+    pub last_sample: i32,
+    pub dc_flagged: bool,
+    pub discontinuity_flagged: bool,
+    /// Becomes `true` after the first sample, so that sample isn't compared against a
+    /// fabricated `last_sample` of 0.
+    pub primed: bool,
+}
+
+impl IntegrityState {
+    pub fn new() -> Self {
+        Self {
+            running_mean: 0.0,
+            last_sample: 0,
+            dc_flagged: false,
+            discontinuity_flagged: false,
+            primed: false,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DcOffsetEvent {
+    pub channel: usize,
+    #[serde(rename = "meanOffset")]
+    pub mean_offset: f64,
+    pub timestamp: String,
+}
+
+#[derive(Serialize)]
+pub struct DiscontinuityEvent {
+    pub channel: usize,
+    pub magnitude: f64,
+    pub timestamp: String,
+}
+
+pub struct IntegrityAnalyser {
+    states: Vec<IntegrityState>,
+    sample_rate: i32,
+    dc_offset_threshold: f64,
+    discontinuity_threshold: f64,
+    contains_defect: bool,
+    dc_offsets: Vec<DcOffsetEvent>,
+    discontinuities: Vec<DiscontinuityEvent>,
+}
+
+impl IntegrityAnalyser {
+    pub fn new(args: &crate::cli::Cli, source: &dyn FrameSource) -> Self {
+        Self {
+            states: vec![IntegrityState::new(); source.n_channels().into()],
+            sample_rate: source.sample_rate(),
+            dc_offset_threshold: args.dc_offset_threshold,
+            discontinuity_threshold: args.discontinuity_threshold,
+            contains_defect: false,
+            dc_offsets: Vec::new(),
+            discontinuities: Vec::new(),
+        }
+    }
+}
+
+impl Analyser for IntegrityAnalyser {
+    fn analyse(&mut self, label: &str, frame_counter: usize, frame: &Samples<i32>) {
+        for (channel_index, sample) in frame.iter().enumerate() {
+            assert!(channel_index < self.states.len());
+            let state = &mut self.states[channel_index];
+            let normalized = *sample as f64 / i32::MAX as f64;
+
+            state.running_mean =
+                state.running_mean + DC_OFFSET_SMOOTHING * (normalized - state.running_mean);
+
+            if state.running_mean.abs() > self.dc_offset_threshold {
+                if !state.dc_flagged {
+                    state.dc_flagged = true;
+                    self.contains_defect = true;
+                    output!(
+                        "[{}] DC OFFSET    : CH:{} - mean {:.4} @ {}",
+                        label,
+                        channel_index,
+                        state.running_mean,
+                        frame_to_time(frame_counter, self.sample_rate)
+                    );
+                    self.dc_offsets.push(DcOffsetEvent {
+                        channel: channel_index,
+                        mean_offset: state.running_mean,
+                        timestamp: frame_to_time(frame_counter, self.sample_rate),
+                    });
+                }
+            } else {
+                state.dc_flagged = false;
+            }
+
+            if state.primed {
+                let jump = (normalized - state.last_sample as f64 / i32::MAX as f64).abs();
+                if jump > self.discontinuity_threshold {
+                    if !state.discontinuity_flagged {
+                        state.discontinuity_flagged = true;
+                        self.contains_defect = true;
+                        output!(
+                            "[{}] DISCONTINUITY: CH:{} - jump {:.4} @ {}",
+                            label,
+                            channel_index,
+                            jump,
+                            frame_to_time(frame_counter, self.sample_rate)
+                        );
+                        self.discontinuities.push(DiscontinuityEvent {
+                            channel: channel_index,
+                            magnitude: jump,
+                            timestamp: frame_to_time(frame_counter, self.sample_rate),
+                        });
+                    }
+                } else {
+                    state.discontinuity_flagged = false;
+                }
+            }
+
+            state.last_sample = *sample;
+            state.primed = true;
+        }
+    }
+
+    fn finish(&mut self, _label: &str) -> u8 {
+        if self.contains_defect {
+            crate::ERR_CONTAINS_INTEGRITY_DEFECT
+        } else {
+            0
+        }
+    }
+
+    fn json(&self) -> Option<(String, serde_json::Value)> {
+        if self.dc_offsets.is_empty() && self.discontinuities.is_empty() {
+            return None;
+        }
+
+        let analysis = serde_json::json!({
+            "dcOffsets": self.dc_offsets,
+            "discontinuities": self.discontinuities,
+            "dcOffsetThreshold": self.dc_offset_threshold,
+            "discontinuityThreshold": self.discontinuity_threshold,
+        });
+
+        Some(("integrity".to_string(), analysis))
+    }
+}
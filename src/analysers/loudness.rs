@@ -0,0 +1,124 @@
+use ebur128::{EbuR128, Error as EbuR128Error, Mode};
+use serde::Serialize;
+use wavers::Samples;
+
+use super::Analyser;
+use crate::source::FrameSource;
+use crate::{debug, output};
+
+#[derive(Serialize)]
+pub struct LoudnessReport {
+    /// `None` when the measurement never produced a finite value (e.g. silent or very short input).
+    #[serde(rename = "integratedLufs")]
+    pub integrated_lufs: Option<f64>,
+    #[serde(rename = "maxMomentaryLufs")]
+    pub max_momentary_lufs: Option<f64>,
+    #[serde(rename = "maxShortTermLufs")]
+    pub max_short_term_lufs: Option<f64>,
+    #[serde(rename = "loudnessRangeLu")]
+    pub loudness_range_lu: f64,
+    pub target: f64,
+    pub tolerance: f64,
+}
+
+pub struct LoudnessAnalyser {
+    loudness: EbuR128,
+    target: f64,
+    tolerance: f64,
+    max_momentary: f64,
+    max_short_term: f64,
+    report: Option<LoudnessReport>,
+}
+
+impl LoudnessAnalyser {
+    pub fn new(args: &crate::cli::Cli, source: &dyn FrameSource) -> Result<Self, EbuR128Error> {
+        let loudness = EbuR128::new(
+            source.n_channels().into(),
+            source.sample_rate() as u32,
+            Mode::I | Mode::S | Mode::M | Mode::LRA,
+        )?;
+
+        Ok(Self {
+            loudness,
+            target: args.target,
+            tolerance: args.tolerance,
+            max_momentary: f64::NEG_INFINITY,
+            max_short_term: f64::NEG_INFINITY,
+            report: None,
+        })
+    }
+}
+
+/// Turns a loudness measurement's `NEG_INFINITY` sentinel (silent/too-short input) into `None`,
+/// since serde_json would otherwise collapse the non-finite value to `null`.
+fn finite(lufs: f64) -> Option<f64> {
+    if lufs.is_finite() { Some(lufs) } else { None }
+}
+
+/// Formats a possibly-absent LUFS value for display, since `{:04.3}` can't be applied to `None`.
+fn fmt_lufs(lufs: Option<f64>) -> String {
+    match lufs {
+        Some(lufs) => format!("{lufs:04.3}"),
+        None => "n/a".to_string(),
+    }
+}
+
+impl Analyser for LoudnessAnalyser {
+    fn analyse(&mut self, label: &str, _frame_counter: usize, frame: &Samples<i32>) {
+        if let Err(err) = self.loudness.add_frames_i32(frame) {
+            debug!(
+                "[{}] DEBUG        : error adding frame to loudness measurement: {:?}",
+                label, &err
+            );
+            return;
+        }
+
+        if let Ok(momentary) = self.loudness.loudness_momentary() {
+            self.max_momentary = self.max_momentary.max(momentary);
+        }
+        if let Ok(short_term) = self.loudness.loudness_shortterm() {
+            self.max_short_term = self.max_short_term.max(short_term);
+        }
+    }
+
+    fn finish(&mut self, label: &str) -> u8 {
+        let integrated = finite(self.loudness.loudness_global().unwrap_or(f64::NEG_INFINITY));
+        let max_momentary = finite(self.max_momentary);
+        let max_short_term = finite(self.max_short_term);
+        let range = self.loudness.loudness_range().unwrap_or(0.0);
+
+        output!(
+            "[{}] LOUDNESS     : LUFS-I: {}; max LUFS-M: {}; max LUFS-S: {}; LRA: {:04.3} LU",
+            label,
+            fmt_lufs(integrated),
+            fmt_lufs(max_momentary),
+            fmt_lufs(max_short_term),
+            range
+        );
+
+        self.report = Some(LoudnessReport {
+            integrated_lufs: integrated,
+            max_momentary_lufs: max_momentary,
+            max_short_term_lufs: max_short_term,
+            loudness_range_lu: range,
+            target: self.target,
+            tolerance: self.tolerance,
+        });
+
+        match integrated {
+            Some(integrated) if (integrated - self.target).abs() > self.tolerance => {
+                output!(
+                    "[{}] OUT OF SPEC  : LUFS-I: {:04.3} is outside {} +/- {} LUFS",
+                    label, integrated, self.target, self.tolerance
+                );
+                crate::ERR_OUT_OF_SPEC
+            }
+            _ => 0,
+        }
+    }
+
+    fn json(&self) -> Option<(String, serde_json::Value)> {
+        let report = self.report.as_ref()?;
+        Some(("loudness".to_string(), serde_json::to_value(report).unwrap()))
+    }
+}
@@ -0,0 +1,149 @@
+use ebur128::{EbuR128, Error as EbuR128Error, Mode};
+use serde::Serialize;
+use wavers::Samples;
+
+use super::Analyser;
+use crate::source::FrameSource;
+use crate::{debug, output, output::frame_to_time};
+
+#[derive(Debug, Clone)]
+pub struct PeakState {
+    /// Whether the current run of blocks is already over `threshold`, so a sustained
+    /// overshoot is reported once rather than on every block until it drops back down.
+    pub over: bool,
+}
+
+impl PeakState {
+    pub fn new() -> Self {
+        Self { over: false }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PeakOvershoot {
+    pub channel: usize,
+    #[serde(rename = "dbtp")]
+    pub dbtp: f64,
+    pub timestamp: String,
+}
+
+#[derive(Serialize)]
+pub struct ChannelPeaks {
+    pub channel: usize,
+    #[serde(rename = "samplePeakDb")]
+    pub sample_peak_db: f64,
+    #[serde(rename = "truePeakDbtp")]
+    pub true_peak_dbtp: f64,
+}
+
+pub struct PeakAnalyser {
+    loudness: EbuR128,
+    sample_rate: i32,
+    states: Vec<PeakState>,
+    threshold: f64,
+    overshoots: Vec<PeakOvershoot>,
+}
+
+fn to_db(linear: f64) -> f64 {
+    20.0 * linear.abs().log10()
+}
+
+impl PeakAnalyser {
+    pub fn new(args: &crate::cli::Cli, source: &dyn FrameSource) -> Result<Self, EbuR128Error> {
+        let loudness = EbuR128::new(
+            source.n_channels().into(),
+            source.sample_rate() as u32,
+            Mode::SAMPLE_PEAK | Mode::TRUE_PEAK,
+        )?;
+
+        Ok(Self {
+            loudness,
+            sample_rate: source.sample_rate(),
+            states: vec![PeakState::new(); source.n_channels().into()],
+            threshold: args.true_peak_threshold,
+            overshoots: Vec::new(),
+        })
+    }
+}
+
+impl Analyser for PeakAnalyser {
+    fn analyse(&mut self, label: &str, frame_counter: usize, frame: &Samples<i32>) {
+        if let Err(err) = self.loudness.add_frames_i32(frame) {
+            debug!(
+                "[{}] DEBUG        : error adding frame to peak measurement: {:?}",
+                label, &err
+            );
+            return;
+        }
+
+        for (channel_index, state) in self.states.iter_mut().enumerate() {
+            // `prev_true_peak` is the peak of the block just added, unlike the cumulative
+            // `true_peak`, so it reflects the current block rather than the running max.
+            let true_peak = self
+                .loudness
+                .prev_true_peak(channel_index as u32)
+                .unwrap_or(0.0);
+            let dbtp = to_db(true_peak);
+
+            if dbtp > self.threshold {
+                if !state.over {
+                    state.over = true;
+                    output!(
+                        "[{}] OVERSHOOT    : CH:{} - {:04.3} dBTP @ {}",
+                        label,
+                        channel_index,
+                        dbtp,
+                        frame_to_time(frame_counter, self.sample_rate)
+                    );
+                    self.overshoots.push(PeakOvershoot {
+                        channel: channel_index,
+                        dbtp,
+                        timestamp: frame_to_time(frame_counter, self.sample_rate),
+                    });
+                }
+            } else {
+                state.over = false;
+            }
+        }
+    }
+
+    fn finish(&mut self, _label: &str) -> u8 {
+        if self.overshoots.is_empty() {
+            0
+        } else {
+            crate::ERR_CONTAINS_CLIPPING
+        }
+    }
+
+    fn json(&self) -> Option<(String, serde_json::Value)> {
+        let peaks: Vec<ChannelPeaks> = self
+            .states
+            .iter()
+            .enumerate()
+            .map(|(channel_index, _)| {
+                let sample_peak = self
+                    .loudness
+                    .sample_peak(channel_index as u32)
+                    .unwrap_or(0.0);
+                let true_peak = self
+                    .loudness
+                    .true_peak(channel_index as u32)
+                    .unwrap_or(0.0);
+
+                ChannelPeaks {
+                    channel: channel_index,
+                    sample_peak_db: to_db(sample_peak),
+                    true_peak_dbtp: to_db(true_peak),
+                }
+            })
+            .collect();
+
+        let analysis = serde_json::json!({
+            "peaks": peaks,
+            "overshoots": self.overshoots,
+            "threshold": self.threshold,
+        });
+
+        Some(("peak".to_string(), analysis))
+    }
+}
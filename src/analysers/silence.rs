@@ -1,8 +1,9 @@
 use ebur128::{EbuR128, Error as EbuR128Error, Mode};
 use serde::Serialize;
-use wavers::{Samples, Wav};
+use wavers::Samples;
 
 use super::Analyser;
+use crate::source::FrameSource;
 use crate::{debug, output, output::frame_to_time};
 
 #[derive(Debug, Clone)]
@@ -44,9 +45,10 @@ pub struct SilenceAnalyser {
     count: usize,
     frame_buf: Vec<i32>,
     frame_buf_iter: usize,
+    frames_seen: usize,
     loudness: EbuR128,
     lufs: f64,
-    num_frames: usize,
+    num_frames: Option<usize>,
     percentage: f32,
     sample_rate: i32,
     state: SilenceState,
@@ -55,24 +57,24 @@ pub struct SilenceAnalyser {
 }
 
 impl SilenceAnalyser {
-    pub fn new(args: &crate::cli::Cli, wav: &Wav<i32>) -> Result<Self, EbuR128Error> {
-        let (_, spec) = wav.wav_spec();
-        let sample_rate = spec.fmt_chunk.sample_rate;
+    pub fn new(args: &crate::cli::Cli, source: &dyn FrameSource) -> Result<Self, EbuR128Error> {
+        let sample_rate = source.sample_rate();
         let loudness = EbuR128::new(
-            wav.n_channels().into(),
+            source.n_channels().into(),
             sample_rate as u32,
             Mode::S | Mode::I,
         )?;
 
-        let window_size = sample_rate as usize * wav.n_channels() as usize;
+        let window_size = sample_rate as usize * source.n_channels() as usize;
 
         Ok(Self {
             count: 0,
             frame_buf: vec![0; window_size],
             frame_buf_iter: 0,
+            frames_seen: 0,
             loudness,
             lufs: args.lufs,
-            num_frames: wav.n_samples(),
+            num_frames: source.n_samples(),
             percentage: args.silence_percentage as f32,
             sample_rate,
             state: SilenceState::new(),
@@ -80,10 +82,17 @@ impl SilenceAnalyser {
             segments: Vec::new(),
         })
     }
+
+    /// Total frame count: the known length if available, otherwise the frames observed so far.
+    fn total_frames(&self) -> usize {
+        self.num_frames.unwrap_or(self.frames_seen)
+    }
 }
 
 impl Analyser for SilenceAnalyser {
     fn analyse(&mut self, label: &str, frame_counter: usize, frame: &Samples<i32>) {
+        self.frames_seen = frame_counter + 1;
+
         for sample in frame.iter() {
             self.frame_buf[self.frame_buf_iter] = *sample;
             self.frame_buf_iter += 1;
@@ -130,7 +139,7 @@ impl Analyser for SilenceAnalyser {
                     lufs,
                     self.loudness.loudness_global().unwrap_or(-f64::INFINITY),
                     frame_to_time(frame_counter, self.sample_rate),
-                    (self.count as f32 / self.num_frames as f32) * 100.0
+                    (self.count as f32 / self.total_frames() as f32) * 100.0
                 );
 
                 if let Some(segment) = self.segments.last_mut() {
@@ -151,22 +160,22 @@ impl Analyser for SilenceAnalyser {
 
     fn finish(&mut self, label: &str) -> u8 {
         if self.state.previous_lufs < self.lufs {
-            let end_frame = self.num_frames;
+            let end_frame = self.total_frames();
             let count = self.count + end_frame - self.state.silence_start_frame;
             output!(
                 "[{}] SILENCE END  : LUFS-S: {:04.3}; LUFS-I: {:04.3} @ {} ({:04.3}% of total)",
                 label,
                 self.state.previous_lufs,
                 self.loudness.loudness_global().unwrap_or(-f64::INFINITY),
-                frame_to_time(self.num_frames, self.sample_rate),
-                (count as f32 / self.num_frames as f32) * 100.0
+                frame_to_time(end_frame, self.sample_rate),
+                (count as f32 / end_frame as f32) * 100.0
             );
 
             if let Some(segment) = self.segments.last_mut() {
                 segment.end = Some(end_frame);
             }
 
-            if (count as f32 / self.num_frames as f32) * 100.0 >= self.percentage {
+            if (count as f32 / end_frame as f32) * 100.0 >= self.percentage {
                 return crate::ERR_CONTAINS_SILENCE;
             }
         }
@@ -179,11 +188,12 @@ impl Analyser for SilenceAnalyser {
             return None;
         }
 
+        let total_frames = self.total_frames();
         let segments: Vec<SilenceSegment> = self
             .segments
             .iter()
             .map(|seg| {
-                let end_frame = seg.end.unwrap_or(self.num_frames);
+                let end_frame = seg.end.unwrap_or(total_frames);
                 let duration_samples = end_frame - seg.start;
                 SilenceSegment {
                     start: seg.start as f32 / self.sample_rate as f32,
@@ -1,6 +1,7 @@
-use wavers::{Samples, Wav};
+use wavers::Samples;
 
 use super::Analyser;
+use crate::source::FrameSource;
 use crate::{debug, output, output::frame_to_time};
 
 #[derive(Debug, Clone)]
@@ -11,25 +12,27 @@ pub struct DetectorState {
 
 pub struct UnderrunAnalyser {
     contains_underrun: bool,
-    num_frames: usize,
+    frames_seen: usize,
+    num_frames: Option<usize>,
     states: Vec<DetectorState>,
     sample_rate: i32,
     samples: usize,
 }
 
 impl UnderrunAnalyser {
-    pub fn new(args: &crate::cli::Cli, wav: &Wav<i32>) -> Self {
+    pub fn new(args: &crate::cli::Cli, source: &dyn FrameSource) -> Self {
         Self {
             contains_underrun: false,
-            num_frames: wav.n_samples(),
+            frames_seen: 0,
+            num_frames: source.n_samples(),
             states: vec![
                 DetectorState {
                     underrun_count: 0,
                     underrun_prev_index: 0,
                 };
-                wav.n_channels().into()
+                source.n_channels().into()
             ],
-            sample_rate: wav.wav_spec().1.fmt_chunk.sample_rate,
+            sample_rate: source.sample_rate(),
             samples: args.samples,
         }
     }
@@ -37,6 +40,8 @@ impl UnderrunAnalyser {
 
 impl Analyser for UnderrunAnalyser {
     fn analyse(&mut self, label: &str, frame_counter: usize, frame: &Samples<i32>) {
+        self.frames_seen = frame_counter + 1;
+
         for (channel_index, sample) in frame.iter().enumerate() {
             assert!(channel_index < self.states.len());
             let state = &mut self.states[channel_index];
@@ -75,14 +80,15 @@ impl Analyser for UnderrunAnalyser {
         }
     }
 
-    fn finish(&self, label: &str) -> u8 {
+    fn finish(&mut self, label: &str) -> u8 {
+        let num_frames = self.num_frames.unwrap_or(self.frames_seen);
         let mut contains_underrun = self.contains_underrun;
         for (channel_index, state) in self.states.iter().enumerate() {
             if state.underrun_count >= self.samples {
                 contains_underrun = true;
                 let underrun_start =
-                    frame_to_time(self.num_frames - state.underrun_count, self.sample_rate);
-                let underrun_end = frame_to_time(self.num_frames, self.sample_rate);
+                    frame_to_time(num_frames - state.underrun_count, self.sample_rate);
+                let underrun_end = frame_to_time(num_frames, self.sample_rate);
                 let underrun_duration = state.underrun_count as f32 / self.sample_rate as f32;
                 output!(
                     "[{}] UNDERRUN     : CH:{} - {} samples ({:06.3}s) {} -> {}",
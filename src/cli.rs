@@ -0,0 +1,103 @@
+use clap::Parser;
+
+/// Validates `--stream-bit-depth`, since `read_raw_sample` only knows how to frame 16/24/32-bit PCM.
+fn parse_stream_bit_depth(raw: &str) -> Result<u16, String> {
+    match raw.parse::<u16>() {
+        Ok(depth @ (16 | 24 | 32)) => Ok(depth),
+        Ok(depth) => Err(format!(
+            "invalid stream bit depth `{depth}`: must be 16, 24, or 32"
+        )),
+        Err(_) => Err(format!("invalid stream bit depth `{raw}`: must be 16, 24, or 32")),
+    }
+}
+
+/// Audio quality-control analyser for WAV, FLAC, MP3, OGG and MP4 files or raw PCM on stdin:
+/// silence, underrun, peak, loudness, spectral-feature and integrity checks.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct Cli {
+    /// Path to the input file to analyse. Omit when --stream is set.
+    #[arg(required_unless_present = "stream")]
+    pub input: Option<String>,
+
+    /// Enable silence detection via EBU R128 short-term loudness gating
+    #[arg(short, long)]
+    pub silence: bool,
+
+    /// Short-term loudness threshold below which audio is considered silent (LUFS-S)
+    #[arg(long, default_value_t = -60.0)]
+    pub lufs: f64,
+
+    /// Percentage of the file that must be silent to report ERR_CONTAINS_SILENCE; checked only
+    /// when the input ends in silence
+    #[arg(long, default_value_t = 1.0)]
+    pub silence_percentage: f64,
+
+    /// Enable zero-sample underrun detection
+    #[arg(short, long)]
+    pub underrun: bool,
+
+    /// Number of consecutive zero samples on a channel that count as an underrun
+    #[arg(long, default_value_t = 512)]
+    pub samples: usize,
+
+    /// Enable DC-offset and sample-to-sample discontinuity (click/glitch) detection
+    #[arg(short = 'g', long)]
+    pub integrity: bool,
+
+    /// Fraction of full scale a channel's running mean may drift before it's flagged as DC offset
+    #[arg(long, default_value_t = 0.02)]
+    pub dc_offset_threshold: f64,
+
+    /// Fraction of full scale a sample-to-sample jump may exceed before it's flagged as a discontinuity
+    #[arg(long, default_value_t = 0.25)]
+    pub discontinuity_threshold: f64,
+
+    /// Enable sample-peak and true-peak clipping detection
+    #[arg(short, long)]
+    pub peak: bool,
+
+    /// True-peak threshold above which an OVERSHOOT is reported (dBTP)
+    #[arg(long, default_value_t = -1.0)]
+    pub true_peak_threshold: f64,
+
+    /// Enable a full EBU R128 loudness compliance report (LUFS-I, LUFS-M, LUFS-S, LRA)
+    #[arg(short = 'r', long)]
+    pub loudness: bool,
+
+    /// Target integrated loudness for compliance checking (LUFS)
+    #[arg(long, default_value_t = -23.0)]
+    pub target: f64,
+
+    /// Allowed deviation from the target before ERR_OUT_OF_SPEC is reported (LU)
+    #[arg(long, default_value_t = 0.5)]
+    pub tolerance: f64,
+
+    /// Enable spectral feature extraction (centroid, rolloff, zero-crossing rate, tempo) for fingerprinting
+    #[arg(long)]
+    pub features: bool,
+
+    /// Read raw interleaved PCM from stdin and analyse it continuously instead of opening a file
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Sample rate of the raw PCM arriving on stdin in --stream mode
+    #[arg(long, default_value_t = 48_000)]
+    pub stream_sample_rate: u32,
+
+    /// Channel count of the raw PCM arriving on stdin in --stream mode
+    #[arg(long, default_value_t = 2)]
+    pub stream_channels: u16,
+
+    /// Bit depth of the raw PCM arriving on stdin in --stream mode (16, 24, or 32)
+    #[arg(long, default_value_t = 16, value_parser = parse_stream_bit_depth)]
+    pub stream_bit_depth: u16,
+
+    /// Disable the progress bar
+    #[arg(long)]
+    pub no_progress: bool,
+
+    /// Write a machine-readable JSON report to this path
+    #[arg(long)]
+    pub json: Option<String>,
+}
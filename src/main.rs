@@ -1,68 +1,155 @@
 mod analysers;
 mod cli;
+mod json;
 mod output;
+mod source;
 
 use clap::Parser;
 use std::process::ExitCode;
-use wavers::{Wav, WaversResult};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use analysers::{Analyser, silence::SilenceAnalyser};
 use cli::Cli;
 use output::{fmt_frame, init_output};
 
+use crate::analysers::features::FeatureAnalyser;
+use crate::analysers::integrity::IntegrityAnalyser;
+use crate::analysers::loudness::LoudnessAnalyser;
+use crate::analysers::peak::PeakAnalyser;
 use crate::analysers::underruns::UnderrunAnalyser;
+use crate::source::FrameSource;
+
+/// How often (in frames) a streaming run prints a rolling status line.
+const STREAM_STATUS_INTERVAL_FRAMES: usize = 1000;
 
 const ERR_CONTAINS_UNDERRUN: u8 = 0b0001;
 const ERR_CONTAINS_SILENCE: u8 = 0b0010;
-
-fn analyse(args: &Cli, wav: &mut Wav<i32>) -> u8 {
+const ERR_CONTAINS_CLIPPING: u8 = 0b0100;
+const ERR_OUT_OF_SPEC: u8 = 0b1000;
+const ERR_CONTAINS_INTEGRITY_DEFECT: u8 = 0b1_0000;
+
+fn analyse(
+    args: &Cli,
+    source: &mut dyn FrameSource,
+    interrupted: &AtomicBool,
+) -> (u8, Vec<Box<dyn Analyser>>) {
     let mut return_code = 0;
 
-    let mut analysers: Vec<Box<dyn Analyser>> = vec![
-        Box::new(SilenceAnalyser::new(args, wav).expect("Could not initialize EbuR128")),
-        Box::new(UnderrunAnalyser::new(args, wav)),
-    ];
+    let mut analysers: Vec<Box<dyn Analyser>> = Vec::new();
+
+    if args.silence {
+        analysers.push(Box::new(
+            SilenceAnalyser::new(args, source).expect("Could not initialize EbuR128"),
+        ));
+    }
+
+    if args.underrun {
+        analysers.push(Box::new(UnderrunAnalyser::new(args, source)));
+    }
+
+    if args.peak {
+        analysers.push(Box::new(
+            PeakAnalyser::new(args, source).expect("Could not initialize EbuR128"),
+        ));
+    }
+
+    if args.loudness {
+        analysers.push(Box::new(
+            LoudnessAnalyser::new(args, source).expect("Could not initialize EbuR128"),
+        ));
+    }
+
+    if args.features {
+        analysers.push(Box::new(FeatureAnalyser::new(args, source)));
+    }
+
+    if args.integrity {
+        analysers.push(Box::new(IntegrityAnalyser::new(args, source)));
+    }
+
+    let num_frames = source.n_samples();
+    let digits = num_frames.map(|n| n.to_string().len()).unwrap_or(10);
+    let sample_rate = source.sample_rate();
+    let frames = source.frames();
 
-    let digits = wav.n_samples().to_string().len();
-    let num_frames = wav.n_samples();
-    let frames = wav.frames();
+    let mut last_frame_counter = 0;
 
     for (frame_counter, frame) in frames.enumerate() {
+        if interrupted.load(Ordering::SeqCst) {
+            break;
+        }
+
+        last_frame_counter = frame_counter;
         let frame_label = fmt_frame(frame_counter, digits);
         output::inc();
 
         for analyser in analysers.iter_mut() {
             analyser.analyse(&frame_label, frame_counter, &frame);
         }
+
+        if num_frames.is_none() && frame_counter % STREAM_STATUS_INTERVAL_FRAMES == 0 {
+            output::set_status(&format!(
+                "@ {}",
+                output::frame_to_time(frame_counter, sample_rate)
+            ));
+        }
     }
 
-    let frame_label = fmt_frame(num_frames, digits);
+    let final_frame = num_frames.unwrap_or(last_frame_counter);
+    let frame_label = fmt_frame(final_frame, digits);
 
-    for analyser in analysers.iter() {
+    for analyser in analysers.iter_mut() {
         return_code |= analyser.finish(&frame_label);
     }
 
-    return_code
+    (return_code, analysers)
 }
 
 fn main() -> ExitCode {
     let args = Cli::parse();
-    let Ok(mut wav): WaversResult<Wav<i32>> = Wav::from_path(&args.input) else {
-        println!("Could not open file: {}", args.input);
-        return ExitCode::from(1);
+    let mut source = if args.stream {
+        source::open_stream(&args)
+    } else {
+        let path = args.input.as_deref().expect("input required when not streaming");
+        match source::open(path) {
+            Ok(source) => source,
+            Err(err) => {
+                println!("Could not open file: {path} ({err})");
+                return ExitCode::from(1);
+            }
+        }
     };
 
-    if !args.underrun && !args.silence {
-        println!("Neither underrun nor silence detection is active, exiting.");
+    if !args.underrun
+        && !args.silence
+        && !args.peak
+        && !args.loudness
+        && !args.features
+        && !args.integrity
+    {
+        println!(
+            "Neither underrun, silence, peak, loudness, feature extraction, nor integrity detection is active, exiting."
+        );
         return ExitCode::from(1);
     }
 
-    let (_, spec) = wav.wav_spec();
-    init_output(&args, wav.n_samples() as u64);
+    let interrupted = Arc::new(AtomicBool::new(false));
+    if args.stream {
+        let interrupted = interrupted.clone();
+        ctrlc::set_handler(move || interrupted.store(true, Ordering::SeqCst))
+            .expect("Could not set SIGINT handler");
+    }
+
+    let num_frames = source.n_samples();
+    init_output(&args, num_frames.map(|n| n as u64));
 
-    output!("[+] sample rate:        {}", &spec.fmt_chunk.sample_rate);
-    output!("[+] channels:           {}", wav.n_channels());
-    output!("[+] total samples:      {}", wav.n_samples());
+    output!("[+] sample rate:        {}", source.sample_rate());
+    output!("[+] channels:           {}", source.n_channels());
+    match num_frames {
+        Some(num_frames) => output!("[+] total samples:      {}", num_frames),
+        None => output!("[+] total samples:      unbounded (streaming)"),
+    }
 
     if args.silence {
         output!("[+] silence threshold:  {} LUFS-S", &args.lufs);
@@ -70,9 +157,28 @@ fn main() -> ExitCode {
     if args.underrun {
         output!("[+] underrun threshold: {} samples", &args.samples);
     }
+    if args.peak {
+        output!("[+] peak threshold:     {} dBTP", &args.true_peak_threshold);
+    }
+    if args.loudness {
+        output!(
+            "[+] loudness target:    {} +/- {} LUFS",
+            &args.target, &args.tolerance
+        );
+    }
+    if args.features {
+        output!("[+] feature extraction: enabled");
+    }
+    if args.integrity {
+        output!(
+            "[+] integrity thresholds: DC {} / jump {}",
+            &args.dc_offset_threshold, &args.discontinuity_threshold
+        );
+    }
 
-    let code = analyse(&args, &mut wav);
+    let (code, analysers) = analyse(&args, source.as_mut(), &interrupted);
 
+    json::write_json(&args, &analysers);
     output::finish();
 
     ExitCode::from(code)
@@ -19,22 +19,41 @@ pub struct Output {
 }
 
 impl Output {
-    pub fn new(args: &Cli, num_frames: u64) -> Self {
+    pub fn new(args: &Cli, num_frames: Option<u64>) -> Self {
         let progress_bar = if args.no_progress {
             None
         } else {
-            Some(ProgressBar::new(num_frames))
+            match num_frames {
+                Some(num_frames) => Some(ProgressBar::new(num_frames)),
+                None => Some(ProgressBar::new_spinner()),
+            }
         };
 
         if let Some(pb) = &progress_bar {
-            pb.set_style(ProgressStyle::with_template("[{elapsed_precise}] [{wide_bar:.yellow/green}] {percent_precise}% ({pos}/{len})")
-                .unwrap()
-                .progress_chars("#>-"));
+            match num_frames {
+                Some(_) => pb.set_style(
+                    ProgressStyle::with_template(
+                        "[{elapsed_precise}] [{wide_bar:.yellow/green}] {percent_precise}% ({pos}/{len})",
+                    )
+                    .unwrap()
+                    .progress_chars("#>-"),
+                ),
+                None => pb.set_style(
+                    ProgressStyle::with_template("[{elapsed_precise}] {spinner} {pos} frames {msg}")
+                        .unwrap(),
+                ),
+            }
         }
 
         Self { progress_bar }
     }
 
+    pub fn set_status(&self, message: &str) {
+        if let Some(pb) = &self.progress_bar {
+            pb.set_message(message.to_string());
+        }
+    }
+
     pub fn inc(&self) {
         if let Some(pb) = &self.progress_bar {
             pb.inc(1);
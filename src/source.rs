@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Stdin, StdinLock};
+use std::path::Path;
+
+use symphonia::core::audio::{AudioBufferRef, Signal};
+use symphonia::core::codecs::{Decoder, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatReader;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::probe::Hint;
+use wavers::{Samples, Wav};
+
+/// A source of interleaved PCM frames, abstracting over the container/codec the audio is stored in.
+pub trait FrameSource {
+    fn sample_rate(&self) -> i32;
+    fn n_channels(&self) -> u16;
+    /// Total number of frames, when known up front (e.g. not a live/streaming source).
+    fn n_samples(&self) -> Option<usize>;
+    fn frames(&mut self) -> Box<dyn Iterator<Item = Samples<i32>> + '_>;
+}
+
+/// Opens `path` with the decoder appropriate for its extension, falling back to WAV.
+pub fn open(path: &str) -> Result<Box<dyn FrameSource>, String> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    match extension.as_str() {
+        "flac" | "mp3" | "ogg" | "mp4" | "m4a" => {
+            Ok(Box::new(DecodedFrameSource::open(path)?))
+        }
+        _ => {
+            let wav = Wav::<i32>::from_path(path).map_err(|err| err.to_string())?;
+            Ok(Box::new(WavFrameSource::new(wav)))
+        }
+    }
+}
+
+/// Opens a [`StdinFrameSource`] using the raw PCM format declared via `--stream-*` flags.
+pub fn open_stream(args: &crate::cli::Cli) -> Box<dyn FrameSource> {
+    Box::new(StdinFrameSource::new(
+        args.stream_sample_rate as i32,
+        args.stream_channels,
+        args.stream_bit_depth,
+    ))
+}
+
+pub struct WavFrameSource {
+    wav: Wav<i32>,
+}
+
+impl WavFrameSource {
+    pub fn new(wav: Wav<i32>) -> Self {
+        Self { wav }
+    }
+}
+
+impl FrameSource for WavFrameSource {
+    fn sample_rate(&self) -> i32 {
+        self.wav.wav_spec().1.fmt_chunk.sample_rate
+    }
+
+    fn n_channels(&self) -> u16 {
+        self.wav.n_channels()
+    }
+
+    fn n_samples(&self) -> Option<usize> {
+        Some(self.wav.n_samples())
+    }
+
+    fn frames(&mut self) -> Box<dyn Iterator<Item = Samples<i32>> + '_> {
+        Box::new(self.wav.frames())
+    }
+}
+
+/// Decoder-backed source for compressed/lossy containers (FLAC, MP3, OGG, MP4), normalizing
+/// every decoded frame to interleaved `i32` so it can feed the same analysers as `WavFrameSource`.
+pub struct DecodedFrameSource {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    sample_rate: i32,
+    n_channels: u16,
+    n_samples: Option<usize>,
+    pending: VecDeque<Samples<i32>>,
+}
+
+impl DecodedFrameSource {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|err| err.to_string())?;
+        let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let probed = symphonia::default::get_probe()
+            .format(&hint, stream, &Default::default(), &Default::default())
+            .map_err(|err| format!("Could not probe input format: {err}"))?;
+
+        let format = probed.format;
+        let track = format
+            .default_track()
+            .ok_or_else(|| "No default audio track".to_string())?;
+        let track_id = track.id;
+
+        let sample_rate = track
+            .codec_params
+            .sample_rate
+            .ok_or_else(|| "Unknown sample rate".to_string())? as i32;
+        let n_channels = track
+            .codec_params
+            .channels
+            .map(|channels| channels.count() as u16)
+            .ok_or_else(|| "Unknown channel count".to_string())?;
+        let n_samples = track.codec_params.n_frames.map(|n| n as usize);
+
+        let decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|err| format!("Could not create decoder: {err}"))?;
+
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            sample_rate,
+            n_channels,
+            n_samples,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Decodes the next packet belonging to our track, buffering its frames into `pending`.
+    /// Returns `false` once the stream is exhausted.
+    fn decode_next_packet(&mut self) -> bool {
+        loop {
+            let Ok(packet) = self.format.next_packet() else {
+                return false;
+            };
+
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    self.buffer_frames(decoded);
+                    return true;
+                }
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(_) => return false,
+            }
+        }
+    }
+
+    fn buffer_frames(&mut self, decoded: AudioBufferRef) {
+        let channels = decoded.spec().channels.count();
+
+        for frame_index in 0..decoded.frames() {
+            let mut frame = Vec::with_capacity(channels);
+            for channel in 0..channels {
+                let sample = match &decoded {
+                    AudioBufferRef::F32(buf) => {
+                        (buf.chan(channel)[frame_index] * i32::MAX as f32) as i32
+                    }
+                    AudioBufferRef::F64(buf) => {
+                        (buf.chan(channel)[frame_index] * i32::MAX as f64) as i32
+                    }
+                    AudioBufferRef::S32(buf) => buf.chan(channel)[frame_index],
+                    AudioBufferRef::S24(buf) => buf.chan(channel)[frame_index].into_i32() << 8,
+                    AudioBufferRef::S16(buf) => (buf.chan(channel)[frame_index] as i32) << 16,
+                    AudioBufferRef::S8(buf) => (buf.chan(channel)[frame_index] as i32) << 24,
+                    AudioBufferRef::U32(buf) => {
+                        (buf.chan(channel)[frame_index] as i64 - (1i64 << 31)) as i32
+                    }
+                    AudioBufferRef::U24(buf) => {
+                        (buf.chan(channel)[frame_index].into_i32() - (1 << 23)) << 8
+                    }
+                    AudioBufferRef::U16(buf) => {
+                        ((buf.chan(channel)[frame_index] as i32) - (1 << 15)) << 16
+                    }
+                    AudioBufferRef::U8(buf) => {
+                        ((buf.chan(channel)[frame_index] as i32) - (1 << 7)) << 24
+                    }
+                };
+                frame.push(sample);
+            }
+            self.pending.push_back(frame.into());
+        }
+    }
+}
+
+impl FrameSource for DecodedFrameSource {
+    fn sample_rate(&self) -> i32 {
+        self.sample_rate
+    }
+
+    fn n_channels(&self) -> u16 {
+        self.n_channels
+    }
+
+    fn n_samples(&self) -> Option<usize> {
+        self.n_samples
+    }
+
+    fn frames(&mut self) -> Box<dyn Iterator<Item = Samples<i32>> + '_> {
+        Box::new(std::iter::from_fn(move || {
+            if self.pending.is_empty() && !self.decode_next_packet() {
+                return None;
+            }
+            self.pending.pop_front()
+        }))
+    }
+}
+
+fn read_raw_sample(reader: &mut StdinLock, bit_depth: u16) -> std::io::Result<i32> {
+    match bit_depth {
+        16 => {
+            let mut buf = [0u8; 2];
+            reader.read_exact(&mut buf)?;
+            Ok((i16::from_le_bytes(buf) as i32) << 16)
+        }
+        24 => {
+            let mut buf = [0u8; 3];
+            reader.read_exact(&mut buf)?;
+            let sign_extended = i32::from_le_bytes([0, buf[0], buf[1], buf[2]]) >> 8;
+            Ok(sign_extended << 8)
+        }
+        32 => {
+            let mut buf = [0u8; 4];
+            reader.read_exact(&mut buf)?;
+            Ok(i32::from_le_bytes(buf))
+        }
+        // `--stream-bit-depth` is validated by clap up front, so this arm is unreachable.
+        other => unreachable!("Unsupported stream bit depth: {other}"),
+    }
+}
+
+/// Frames raw interleaved PCM read from stdin, one frame at a time, for continuous/live input.
+/// Has no known length, so [`FrameSource::n_samples`] always returns `None`.
+pub struct StdinFrameSource {
+    stdin: Stdin,
+    sample_rate: i32,
+    n_channels: u16,
+    bit_depth: u16,
+}
+
+impl StdinFrameSource {
+    pub fn new(sample_rate: i32, n_channels: u16, bit_depth: u16) -> Self {
+        Self {
+            stdin: std::io::stdin(),
+            sample_rate,
+            n_channels,
+            bit_depth,
+        }
+    }
+}
+
+impl FrameSource for StdinFrameSource {
+    fn sample_rate(&self) -> i32 {
+        self.sample_rate
+    }
+
+    fn n_channels(&self) -> u16 {
+        self.n_channels
+    }
+
+    fn n_samples(&self) -> Option<usize> {
+        None
+    }
+
+    fn frames(&mut self) -> Box<dyn Iterator<Item = Samples<i32>> + '_> {
+        let n_channels = self.n_channels;
+        let bit_depth = self.bit_depth;
+        let mut stdin = self.stdin.lock();
+
+        Box::new(std::iter::from_fn(move || {
+            let mut frame = Vec::with_capacity(n_channels as usize);
+            for _ in 0..n_channels {
+                match read_raw_sample(&mut stdin, bit_depth) {
+                    Ok(sample) => frame.push(sample),
+                    Err(_) => return None,
+                }
+            }
+            Some(frame.into())
+        }))
+    }
+}